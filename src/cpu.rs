@@ -1,35 +1,167 @@
-use registers::Register;
+use registers::Registers;
 mod registers;
-use instruction::Instruction;
+use instruction::{ArithmeticTarget, Instruction, JumpTest, StackTarget};
 mod instruction;
-use memory_bus::MemoryBus;
-mod memory_bus;
+pub use memory_bus::MemoryBus;
+pub mod memory_bus;
+use interrupt::Interrupt;
+mod interrupt;
+pub use variant::Variant;
+pub mod variant;
+pub use debugger::{DebugCommand, DebugResponse, Debugger, Register8, RegisterPair};
+pub mod debugger;
 
-struct CPU {
+pub struct CPU {
     registers: Registers,
     pc: u16,
-    bus: MemoryBus
+    sp: u16,
+    bus: MemoryBus,
+    ime: bool,
+    // Set by EI, which only takes effect after the instruction following it
+    // has executed.
+    ime_scheduled: bool,
+    variant: Variant,
+    // Only ever true on a CGB that has switched speeds via KEY1; a DMG never sets this.
+    double_speed: bool,
+    debugger: Debugger,
+    // Set when pc hits a breakpoint; step() then halts instead of executing.
+    halted: bool,
+    // Set by Resume so the next step() can get past the breakpoint pc is
+    // still sitting on instead of re-halting on it immediately.
+    skip_next_breakpoint_check: bool
 }
 
+// The number of T-cycles it takes to service an interrupt: two wasted
+// machine cycles, a push of pc, and the jump to the vector.
+const INTERRUPT_SERVICE_CYCLES: u8 = 20;
+
 impl CPU {
-    fn step(&mut self) {
+    pub fn new(variant: Variant, bus: MemoryBus) -> CPU {
+        let mut cpu = CPU {
+            registers: Registers { a: 0, b: 0, c: 0, d: 0, e: 0, f: 0.into(), h: 0, l: 0 },
+            pc: 0,
+            sp: 0,
+            bus,
+            ime: false,
+            ime_scheduled: false,
+            variant,
+            double_speed: false,
+            debugger: Debugger::new(),
+            halted: false,
+            skip_next_breakpoint_check: false
+        };
+        cpu.reset();
+        cpu
+    }
+
+    // Restores the power-on state documented for this CPU's variant.
+    // Breakpoints are left untouched since they're a debugging concern, not
+    // part of the emulated hardware's state.
+    pub fn reset(&mut self) {
+        let values = self.variant.power_on_registers();
+        self.registers.a = values.a;
+        self.registers.f = values.f.into();
+        self.registers.b = values.b;
+        self.registers.c = values.c;
+        self.registers.d = values.d;
+        self.registers.e = values.e;
+        self.registers.h = values.h;
+        self.registers.l = values.l;
+
+        self.pc = 0x0100;
+        self.sp = 0xFFFE;
+        self.ime = false;
+        self.ime_scheduled = false;
+        self.double_speed = false;
+        self.halted = false;
+        self.skip_next_breakpoint_check = false;
+    }
+
+    pub fn step(&mut self) -> u8 {
+        if !self.halted && !self.skip_next_breakpoint_check && self.debugger.has_breakpoint(self.pc) {
+            self.halted = true;
+        }
+        self.skip_next_breakpoint_check = false;
+
+        if self.halted {
+            return 0;
+        }
+
+        self.step_inner()
+    }
+
+    // Does the actual fetch/decode/execute; called directly by the
+    // single-step debug command so it can step past a breakpoint.
+    fn step_inner(&mut self) -> u8 {
+        let enable_ime_after_this_instruction = self.ime_scheduled;
+        self.ime_scheduled = false;
+
+        if self.ime {
+            if let Some(interrupt) = self.pending_interrupt() {
+                self.service_interrupt(interrupt);
+                return if self.double_speed {
+                    INTERRUPT_SERVICE_CYCLES / 2
+                } else {
+                    INTERRUPT_SERVICE_CYCLES
+                };
+            }
+        }
+
         let mut instruction_byte = self.bus.read_byte(self.pc);
         let prefixed = instruction_byte == 0xCB;
         if prefixed {
           instruction_byte = self.bus.read_byte(self.pc + 1);
         }
-    
-        let next_pc = if let Some(instruction) = Instruction::from_byte(instruction_byte, prefixed) {
-          self.execute(instruction)
-        } else {
-          let description = format!("0x{}{:x}", if prefixed { "cb" } else { "" }, instruction_byte);
-          panic!("Unkown instruction found for: {}", description)
+
+        let instruction = match Instruction::from_byte(instruction_byte, prefixed) {
+            Some(instruction) => instruction,
+            None => {
+                let description = format!("0x{}{:x}", if prefixed { "cb" } else { "" }, instruction_byte);
+                panic!("Unkown instruction found for: {}", description)
+            }
         };
-    
+
+        // If the instruction we're about to run is DI, it wins over an EI
+        // scheduled by the previous instruction: real hardware leaves
+        // interrupts disabled after `EI; DI`.
+        let just_disabled_interrupts = matches!(instruction, Instruction::DI);
+        let (next_pc, cycles) = self.execute(instruction);
+
         self.pc = next_pc;
+
+        if enable_ime_after_this_instruction && !just_disabled_interrupts {
+            self.ime = true;
+        }
+
+        // In CGB double-speed mode the CPU clock runs twice as fast as the
+        // rest of the hardware, so instructions cost half as many cycles
+        // from the single-speed subsystems' point of view.
+        if self.double_speed {
+            cycles / 2
+        } else {
+            cycles
+        }
+    }
+
+    fn pending_interrupt(&self) -> Option<Interrupt> {
+        let requested = self.bus.interrupt_enable() & self.bus.interrupt_flag();
+        Interrupt::from_bits(requested)
+    }
+
+    fn service_interrupt(&mut self, interrupt: Interrupt) {
+        self.ime = false;
+        self.ime_scheduled = false;
+
+        let flags = self.bus.interrupt_flag() & !interrupt.bit();
+        self.bus.set_interrupt_flag(flags);
+
+        self.push(self.pc);
+        self.pc = interrupt.vector();
     }
 
-    fn execute(&mut self, instruction: Instruction) -> u16 {
+    // Returns the next value of pc together with the number of T-cycles the
+    // instruction took, so callers can keep other subsystems in lockstep.
+    fn execute(&mut self, instruction: Instruction) -> (u16, u8) {
         match instruction {
             Instruction::ADD(target) => match target {
                 ArithmeticTarget::A => todo!(),
@@ -38,23 +170,135 @@ impl CPU {
                     let value = self.registers.c;
                     let new_value = self.add(value);
                     self.registers.a = new_value;
-                    self.pc.wrapping_add(1)
+                    (self.pc.wrapping_add(1), 4)
                 },
                 ArithmeticTarget::D => todo!(),
                 ArithmeticTarget::E => todo!(),
                 ArithmeticTarget::H => todo!(),
                 ArithmeticTarget::L => todo!(),
             },
+            Instruction::SUB(target) => match target {
+                ArithmeticTarget::A => todo!(),
+                ArithmeticTarget::B => todo!(),
+                ArithmeticTarget::C => {
+                    let value = self.registers.c;
+                    let new_value = self.sub(value);
+                    self.registers.a = new_value;
+                    (self.pc.wrapping_add(1), 4)
+                },
+                ArithmeticTarget::D => todo!(),
+                ArithmeticTarget::E => todo!(),
+                ArithmeticTarget::H => todo!(),
+                ArithmeticTarget::L => todo!(),
+            },
+            Instruction::SBC(target) => match target {
+                ArithmeticTarget::A => todo!(),
+                ArithmeticTarget::B => todo!(),
+                ArithmeticTarget::C => {
+                    let value = self.registers.c;
+                    let new_value = self.sbc(value);
+                    self.registers.a = new_value;
+                    (self.pc.wrapping_add(1), 4)
+                },
+                ArithmeticTarget::D => todo!(),
+                ArithmeticTarget::E => todo!(),
+                ArithmeticTarget::H => todo!(),
+                ArithmeticTarget::L => todo!(),
+            },
+            Instruction::CP(target) => match target {
+                ArithmeticTarget::A => todo!(),
+                ArithmeticTarget::B => todo!(),
+                ArithmeticTarget::C => {
+                    let value = self.registers.c;
+                    // CP is a subtraction whose result is discarded; only the flags matter.
+                    self.sub(value);
+                    (self.pc.wrapping_add(1), 4)
+                },
+                ArithmeticTarget::D => todo!(),
+                ArithmeticTarget::E => todo!(),
+                ArithmeticTarget::H => todo!(),
+                ArithmeticTarget::L => todo!(),
+            },
+            Instruction::DAA => {
+                self.daa();
+                (self.pc.wrapping_add(1), 4)
+            }
             Instruction::JP(test) => {
-                let jump_condition = match test {
-                    JumpTest::NotZero => !self.registers.f.zero,
-                    JumpTest::NotCarry => !self.registers.f.carry,
-                    JumpTest::Zero => self.registers.f.zero,
-                    JumpTest::Carry => self.registers.f.carry,
-                    JumpTest::Always => true
+                let jump_condition = self.test_jump_condition(test);
+                let next_pc = self.jump(jump_condition);
+                let cycles = if jump_condition { 16 } else { 12 };
+                (next_pc, cycles)
+            }
+            Instruction::CALL(test) => {
+                let jump_condition = self.test_jump_condition(test);
+                let next_pc = self.call(jump_condition);
+                let cycles = if jump_condition { 24 } else { 12 };
+                (next_pc, cycles)
+            }
+            Instruction::RET(test) => {
+                let jump_condition = self.test_jump_condition(test);
+                let next_pc = self.ret(jump_condition);
+                let cycles = match test {
+                    // An unconditional RET doesn't spend a cycle testing a
+                    // condition, so it's cheaper than a conditional RET taken.
+                    JumpTest::Always => 16,
+                    _ if jump_condition => 20,
+                    _ => 8,
+                };
+                (next_pc, cycles)
+            }
+            Instruction::PUSH(target) => {
+                let value = match target {
+                    StackTarget::BC => self.registers.get_bc(),
+                    StackTarget::DE => self.registers.get_de(),
+                    StackTarget::HL => self.registers.get_hl(),
+                    StackTarget::AF => self.registers.get_af(),
                 };
-                self.jump(jump_condition)
+                self.push(value);
+                (self.pc.wrapping_add(1), 16)
+            }
+            Instruction::POP(target) => {
+                let value = self.pop();
+                match target {
+                    StackTarget::BC => self.registers.set_bc(value),
+                    StackTarget::DE => self.registers.set_de(value),
+                    StackTarget::HL => self.registers.set_hl(value),
+                    StackTarget::AF => self.registers.set_af(value),
+                };
+                (self.pc.wrapping_add(1), 12)
+            }
+            Instruction::EI => {
+                self.ime_scheduled = true;
+                (self.pc.wrapping_add(1), 4)
+            }
+            Instruction::DI => {
+                self.ime = false;
+                self.ime_scheduled = false;
+                (self.pc.wrapping_add(1), 4)
             }
+            Instruction::RETI => {
+                self.ime = true;
+                (self.pop(), 16)
+            }
+            Instruction::STOP => {
+                // STOP is followed by a padding byte; on a CGB it also
+                // commits a speed switch previously armed via KEY1 bit 0.
+                if self.variant.supports_double_speed() && self.bus.key1() & 0x01 != 0 {
+                    self.double_speed = !self.double_speed;
+                    self.bus.set_key1((self.double_speed as u8) << 7);
+                }
+                (self.pc.wrapping_add(2), 4)
+            }
+        }
+    }
+
+    fn test_jump_condition(&self, test: JumpTest) -> bool {
+        match test {
+            JumpTest::NotZero => !self.registers.f.zero,
+            JumpTest::NotCarry => !self.registers.f.carry,
+            JumpTest::Zero => self.registers.f.zero,
+            JumpTest::Carry => self.registers.f.carry,
+            JumpTest::Always => true
         }
     }
 
@@ -70,13 +314,59 @@ impl CPU {
         new_value
     }
 
+    fn sub(&mut self, value: u8) -> u8 {
+        let (new_value, did_overflow) = self.registers.a.overflowing_sub(value);
+        self.registers.f.zero = new_value == 0;
+        self.registers.f.subtract = true;
+        self.registers.f.carry = did_overflow;
+        self.registers.f.half_carry = (self.registers.a & 0xF) < (value & 0xF);
+        new_value
+    }
+
+    fn sbc(&mut self, value: u8) -> u8 {
+        let carry_in = if self.registers.f.carry { 1 } else { 0 };
+        let (partial, first_overflow) = self.registers.a.overflowing_sub(value);
+        let (new_value, second_overflow) = partial.overflowing_sub(carry_in);
+
+        self.registers.f.zero = new_value == 0;
+        self.registers.f.subtract = true;
+        self.registers.f.carry = first_overflow || second_overflow;
+        self.registers.f.half_carry = (self.registers.a & 0xF) < (value & 0xF) + carry_in;
+        new_value
+    }
+
+    // Corrects register a into valid BCD after a preceding ADD/ADC or
+    // SUB/SBC, using the subtract/half_carry/carry flags left behind by it.
+    fn daa(&mut self) {
+        let mut a = self.registers.a;
+        let mut carry = self.registers.f.carry;
+
+        if !self.registers.f.subtract {
+            if self.registers.f.half_carry || (a & 0x0F) > 9 {
+                a = a.wrapping_add(0x06);
+            }
+            if carry || a > 0x99 {
+                a = a.wrapping_add(0x60);
+                carry = true;
+            }
+        } else {
+            if self.registers.f.half_carry {
+                a = a.wrapping_sub(0x06);
+            }
+            if carry {
+                a = a.wrapping_sub(0x60);
+            }
+        }
+
+        self.registers.f.zero = a == 0;
+        self.registers.f.half_carry = false;
+        self.registers.f.carry = carry;
+        self.registers.a = a;
+    }
+
     fn jump(&self, should_jump: bool) -> u16 {
         if should_jump {
-          // Gameboy is little endian so read pc + 2 as most significant bit
-          // and pc + 1 as least significant bit
-          let least_significant_byte = self.bus.read_byte(self.pc + 1) as u16;
-          let most_significant_byte = self.bus.read_byte(self.pc + 2) as u16;
-          (most_significant_byte << 8) | least_significant_byte
+          self.read_next_word()
         } else {
           // If we don't jump we need to still move the program
           // counter forward by 3 since the jump instruction is
@@ -84,4 +374,410 @@ impl CPU {
           self.pc.wrapping_add(3)
         }
     }
+
+    // Gameboy is little endian so read pc + 2 as most significant bit
+    // and pc + 1 as least significant bit
+    fn read_next_word(&self) -> u16 {
+        let least_significant_byte = self.bus.read_byte(self.pc + 1) as u16;
+        let most_significant_byte = self.bus.read_byte(self.pc + 2) as u16;
+        (most_significant_byte << 8) | least_significant_byte
+    }
+
+    // PUSH decrements sp by one, writes the high byte, decrements again,
+    // then writes the low byte.
+    fn push(&mut self, value: u16) {
+        self.sp = self.sp.wrapping_sub(1);
+        self.bus.write_byte(self.sp, ((value & 0xFF00) >> 8) as u8);
+
+        self.sp = self.sp.wrapping_sub(1);
+        self.bus.write_byte(self.sp, (value & 0xFF) as u8);
+    }
+
+    // POP reads the low byte then the high byte, incrementing sp each time.
+    fn pop(&mut self) -> u16 {
+        let least_significant_byte = self.bus.read_byte(self.sp) as u16;
+        self.sp = self.sp.wrapping_add(1);
+
+        let most_significant_byte = self.bus.read_byte(self.sp) as u16;
+        self.sp = self.sp.wrapping_add(1);
+
+        (most_significant_byte << 8) | least_significant_byte
+    }
+
+    fn call(&mut self, should_jump: bool) -> u16 {
+        let next_pc = self.pc.wrapping_add(3);
+        if should_jump {
+            self.push(next_pc);
+            self.read_next_word()
+        } else {
+            next_pc
+        }
+    }
+
+    fn ret(&mut self, should_jump: bool) -> u16 {
+        if should_jump {
+            self.pop()
+        } else {
+            self.pc.wrapping_add(1)
+        }
+    }
+
+    fn disassemble_current_instruction(&self) -> String {
+        let mut instruction_byte = self.bus.read_byte(self.pc);
+        let prefixed = instruction_byte == 0xCB;
+        if prefixed {
+            instruction_byte = self.bus.read_byte(self.pc + 1);
+        }
+
+        match Instruction::from_byte(instruction_byte, prefixed) {
+            Some(instruction) => format!("{:?}", instruction),
+            None => format!("0x{}{:02x} (unknown)", if prefixed { "cb" } else { "" }, instruction_byte),
+        }
+    }
+
+    pub fn execute_command(&mut self, command: DebugCommand) -> DebugResponse {
+        match command {
+            DebugCommand::ReadRegister(register) => DebugResponse::Register8(match register {
+                Register8::A => self.registers.a,
+                Register8::B => self.registers.b,
+                Register8::C => self.registers.c,
+                Register8::D => self.registers.d,
+                Register8::E => self.registers.e,
+                Register8::F => self.registers.f.into(),
+                Register8::H => self.registers.h,
+                Register8::L => self.registers.l,
+            }),
+            DebugCommand::WriteRegister(register, value) => {
+                match register {
+                    Register8::A => self.registers.a = value,
+                    Register8::B => self.registers.b = value,
+                    Register8::C => self.registers.c = value,
+                    Register8::D => self.registers.d = value,
+                    Register8::E => self.registers.e = value,
+                    Register8::F => self.registers.f = value.into(),
+                    Register8::H => self.registers.h = value,
+                    Register8::L => self.registers.l = value,
+                };
+                DebugResponse::Ack
+            }
+            DebugCommand::ReadPair(pair) => DebugResponse::Register16(match pair {
+                RegisterPair::BC => self.registers.get_bc(),
+                RegisterPair::DE => self.registers.get_de(),
+                RegisterPair::HL => self.registers.get_hl(),
+                RegisterPair::AF => self.registers.get_af(),
+            }),
+            DebugCommand::WritePair(pair, value) => {
+                match pair {
+                    RegisterPair::BC => self.registers.set_bc(value),
+                    RegisterPair::DE => self.registers.set_de(value),
+                    RegisterPair::HL => self.registers.set_hl(value),
+                    RegisterPair::AF => self.registers.set_af(value),
+                };
+                DebugResponse::Ack
+            }
+            DebugCommand::ReadPc => DebugResponse::Register16(self.pc),
+            DebugCommand::WritePc(value) => {
+                self.pc = value;
+                DebugResponse::Ack
+            }
+            DebugCommand::ReadSp => DebugResponse::Register16(self.sp),
+            DebugCommand::WriteSp(value) => {
+                self.sp = value;
+                DebugResponse::Ack
+            }
+            DebugCommand::AddBreakpoint(address) => {
+                self.debugger.add_breakpoint(address);
+                DebugResponse::Ack
+            }
+            DebugCommand::RemoveBreakpoint(address) => {
+                self.debugger.remove_breakpoint(address);
+                DebugResponse::Ack
+            }
+            DebugCommand::Resume => {
+                self.halted = false;
+                self.skip_next_breakpoint_check = true;
+                DebugResponse::Ack
+            }
+            DebugCommand::SingleStep => {
+                let cycles = self.step_inner();
+                DebugResponse::Register16(cycles as u16)
+            }
+            DebugCommand::Disassemble => DebugResponse::Mnemonic(self.disassemble_current_instruction()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_cpu() -> CPU {
+        CPU::new(Variant::Dmg, MemoryBus::new())
+    }
+
+    #[test]
+    fn push_then_pop_round_trips_bc_de_hl() {
+        let mut cpu = test_cpu();
+        cpu.sp = 0xFFFE;
+        cpu.registers.set_bc(0x1234);
+        cpu.registers.set_de(0x5678);
+        cpu.registers.set_hl(0x9ABC);
+
+        cpu.execute(Instruction::PUSH(StackTarget::BC));
+        cpu.execute(Instruction::PUSH(StackTarget::DE));
+        cpu.execute(Instruction::PUSH(StackTarget::HL));
+
+        cpu.execute(Instruction::POP(StackTarget::BC));
+        cpu.execute(Instruction::POP(StackTarget::DE));
+        cpu.execute(Instruction::POP(StackTarget::HL));
+
+        // Popped in reverse push order.
+        assert_eq!(cpu.registers.get_bc(), 0x9ABC);
+        assert_eq!(cpu.registers.get_de(), 0x5678);
+        assert_eq!(cpu.registers.get_hl(), 0x1234);
+    }
+
+    #[test]
+    fn push_then_pop_round_trips_af() {
+        let mut cpu = test_cpu();
+        cpu.sp = 0xFFFE;
+        cpu.registers.set_af(0x01B0);
+
+        cpu.execute(Instruction::PUSH(StackTarget::AF));
+        // Clobber a and f so the POP has to be what restores them.
+        cpu.registers.set_af(0x0000);
+        cpu.execute(Instruction::POP(StackTarget::AF));
+
+        assert_eq!(cpu.registers.get_af(), 0x01B0);
+        assert!(cpu.registers.f.zero);
+        assert!(!cpu.registers.f.subtract);
+        assert!(cpu.registers.f.half_carry);
+        assert!(cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn ei_does_not_enable_interrupts_until_after_the_next_instruction() {
+        let mut cpu = test_cpu();
+        cpu.bus.write_byte(cpu.pc, 0xFB); // EI
+        cpu.bus.write_byte(cpu.pc.wrapping_add(1), 0x27); // DAA, a single-byte instruction
+
+        // Executing EI itself doesn't enable interrupts yet.
+        cpu.step_inner();
+        assert!(!cpu.ime);
+
+        // Only after the instruction following EI has run do they turn on.
+        cpu.step_inner();
+        assert!(cpu.ime);
+    }
+
+    #[test]
+    fn di_right_after_ei_leaves_interrupts_disabled() {
+        let mut cpu = test_cpu();
+        cpu.bus.write_byte(cpu.pc, 0xF3); // DI
+        cpu.ime_scheduled = true;
+
+        cpu.step_inner();
+
+        assert!(!cpu.ime);
+        assert!(!cpu.ime_scheduled);
+    }
+
+    #[test]
+    fn jp_costs_more_cycles_when_taken_than_not_taken() {
+        let mut cpu = test_cpu();
+        let (_, taken_cycles) = cpu.execute(Instruction::JP(JumpTest::Always));
+        assert_eq!(taken_cycles, 16);
+
+        cpu.registers.f.zero = false;
+        let (_, not_taken_cycles) = cpu.execute(Instruction::JP(JumpTest::Zero));
+        assert_eq!(not_taken_cycles, 12);
+    }
+
+    #[test]
+    fn call_costs_more_cycles_when_taken_than_not_taken() {
+        let mut cpu = test_cpu();
+        let (_, taken_cycles) = cpu.execute(Instruction::CALL(JumpTest::Always));
+        assert_eq!(taken_cycles, 24);
+
+        cpu.registers.f.carry = false;
+        let (_, not_taken_cycles) = cpu.execute(Instruction::CALL(JumpTest::Carry));
+        assert_eq!(not_taken_cycles, 12);
+    }
+
+    #[test]
+    fn ret_costs_more_cycles_when_taken_than_not_taken_and_least_when_unconditional() {
+        let mut cpu = test_cpu();
+        let (_, unconditional_cycles) = cpu.execute(Instruction::RET(JumpTest::Always));
+        assert_eq!(unconditional_cycles, 16);
+
+        cpu.registers.f.zero = true;
+        let (_, taken_cycles) = cpu.execute(Instruction::RET(JumpTest::Zero));
+        assert_eq!(taken_cycles, 20);
+
+        cpu.registers.f.zero = false;
+        let (_, not_taken_cycles) = cpu.execute(Instruction::RET(JumpTest::Zero));
+        assert_eq!(not_taken_cycles, 8);
+    }
+
+    #[test]
+    fn sub_sets_flags_for_a_borrow() {
+        let mut cpu = test_cpu();
+        cpu.registers.a = 0x10;
+        let new_value = cpu.sub(0x01);
+
+        assert_eq!(new_value, 0x0F);
+        assert!(!cpu.registers.f.zero);
+        assert!(cpu.registers.f.subtract);
+        assert!(cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn sbc_folds_the_carry_flag_into_the_subtraction() {
+        let mut cpu = test_cpu();
+        cpu.registers.a = 0x10;
+        cpu.registers.f.carry = true;
+        let new_value = cpu.sbc(0x0F);
+
+        // 0x10 - 0x0F - 1 (carry-in) = 0x00, with a half-carry from 0x0 < 0xF + 1.
+        assert_eq!(new_value, 0x00);
+        assert!(cpu.registers.f.zero);
+        assert!(cpu.registers.f.subtract);
+        assert!(cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
+
+    #[test]
+    fn daa_corrects_bcd_addition() {
+        let mut cpu = test_cpu();
+        // 0x45 + 0x38 = 0x7D in binary, which should decimal-adjust to 0x83 (45 + 38 = 83).
+        cpu.registers.a = 0x7D;
+        cpu.registers.f.subtract = false;
+        cpu.registers.f.half_carry = true;
+        cpu.registers.f.carry = false;
+
+        cpu.daa();
+
+        assert_eq!(cpu.registers.a, 0x83);
+        assert!(!cpu.registers.f.zero);
+        assert!(!cpu.registers.f.carry);
+        assert!(!cpu.registers.f.half_carry);
+    }
+
+    #[test]
+    fn daa_corrects_bcd_subtraction() {
+        let mut cpu = test_cpu();
+        // 0x45 - 0x38 = 0x0D in binary, which should decimal-adjust to 0x07 (45 - 38 = 7).
+        cpu.registers.a = 0x0D;
+        cpu.registers.f.subtract = true;
+        cpu.registers.f.half_carry = true;
+        cpu.registers.f.carry = false;
+
+        cpu.daa();
+
+        assert_eq!(cpu.registers.a, 0x07);
+        assert!(!cpu.registers.f.zero);
+        assert!(!cpu.registers.f.carry);
+        assert!(!cpu.registers.f.half_carry);
+    }
+
+    #[test]
+    fn dmg_and_cgb_power_on_with_different_registers() {
+        let dmg = test_cpu();
+        assert_eq!(dmg.registers.get_af(), 0x01B0);
+        assert_eq!(dmg.registers.get_hl(), 0x014D);
+
+        let cgb = CPU::new(Variant::Cgb, MemoryBus::new());
+        assert_eq!(cgb.registers.get_af(), 0x1180);
+        assert_eq!(cgb.registers.get_hl(), 0x000D);
+    }
+
+    #[test]
+    fn stop_toggles_double_speed_on_cgb_when_key1_is_armed() {
+        let mut cpu = CPU::new(Variant::Cgb, MemoryBus::new());
+        cpu.bus.set_key1(0x01); // prepare-switch bit armed
+
+        cpu.execute(Instruction::STOP);
+
+        assert!(cpu.double_speed);
+        assert_eq!(cpu.bus.key1() & 0x80, 0x80);
+    }
+
+    #[test]
+    fn stop_does_nothing_on_dmg() {
+        let mut cpu = test_cpu();
+        cpu.bus.set_key1(0x01);
+
+        cpu.execute(Instruction::STOP);
+
+        assert!(!cpu.double_speed);
+    }
+
+    #[test]
+    fn stop_does_nothing_on_cgb_when_key1_is_not_armed() {
+        let mut cpu = CPU::new(Variant::Cgb, MemoryBus::new());
+
+        cpu.execute(Instruction::STOP);
+
+        assert!(!cpu.double_speed);
+    }
+
+    #[test]
+    fn step_halts_on_a_breakpoint_and_resume_gets_past_it() {
+        let mut cpu = test_cpu();
+        let breakpoint = cpu.pc;
+        cpu.bus.write_byte(breakpoint, 0x27); // DAA
+        cpu.debugger.add_breakpoint(breakpoint);
+
+        // Hits the breakpoint instead of executing.
+        let cycles = cpu.step();
+        assert!(cpu.halted);
+        assert_eq!(cycles, 0);
+        assert_eq!(cpu.pc, breakpoint);
+
+        // Resume lets the next step() actually execute past it.
+        cpu.execute_command(DebugCommand::Resume);
+        assert!(!cpu.halted);
+        cpu.step();
+        assert_eq!(cpu.pc, breakpoint.wrapping_add(1));
+
+        // Stepping again re-hits the same breakpoint on the next pass.
+        cpu.pc = breakpoint;
+        cpu.step();
+        assert!(cpu.halted);
+    }
+
+    #[test]
+    fn single_step_executes_through_a_breakpoint() {
+        let mut cpu = test_cpu();
+        let breakpoint = cpu.pc;
+        cpu.bus.write_byte(breakpoint, 0x27); // DAA
+        cpu.debugger.add_breakpoint(breakpoint);
+
+        // Actually hit the breakpoint first, the way a debugger session would.
+        cpu.step();
+        assert!(cpu.halted);
+        assert_eq!(cpu.pc, breakpoint);
+
+        // SingleStep bypasses the halt and executes the instruction directly.
+        let response = cpu.execute_command(DebugCommand::SingleStep);
+
+        assert!(matches!(response, DebugResponse::Register16(4)));
+        assert_eq!(cpu.pc, breakpoint.wrapping_add(1));
+    }
+
+    #[test]
+    fn register_f_round_trips_through_the_debug_command_api() {
+        let mut cpu = test_cpu();
+        cpu.registers.f = 0xB0.into();
+
+        let response = cpu.execute_command(DebugCommand::ReadRegister(Register8::F));
+        assert!(matches!(response, DebugResponse::Register8(0xB0)));
+
+        cpu.execute_command(DebugCommand::WriteRegister(Register8::F, 0x80));
+        assert!(cpu.registers.f.zero);
+        assert!(!cpu.registers.f.subtract);
+        assert!(!cpu.registers.f.half_carry);
+        assert!(!cpu.registers.f.carry);
+    }
 }
\ No newline at end of file
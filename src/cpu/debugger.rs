@@ -0,0 +1,76 @@
+use std::collections::HashSet;
+
+/// Tracks PC breakpoints so `CPU::step` can halt instead of executing once
+/// it reaches one, turning the emulator into something debuggable without a
+/// separate tool.
+pub struct Debugger {
+    breakpoints: HashSet<u16>,
+}
+
+impl Default for Debugger {
+    fn default() -> Debugger {
+        Debugger::new()
+    }
+}
+
+impl Debugger {
+    pub fn new() -> Debugger {
+        Debugger { breakpoints: HashSet::new() }
+    }
+
+    pub fn add_breakpoint(&mut self, address: u16) {
+        self.breakpoints.insert(address);
+    }
+
+    pub fn remove_breakpoint(&mut self, address: u16) {
+        self.breakpoints.remove(&address);
+    }
+
+    pub fn has_breakpoint(&self, address: u16) -> bool {
+        self.breakpoints.contains(&address)
+    }
+}
+
+/// The 8-bit registers a debugger command can read or write directly.
+pub enum Register8 {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    H,
+    L,
+}
+
+/// The "virtual" 16-bit register pairs, addressed through the existing
+/// `get_*`/`set_*` methods on `Registers`.
+pub enum RegisterPair {
+    BC,
+    DE,
+    HL,
+    AF,
+}
+
+pub enum DebugCommand {
+    ReadRegister(Register8),
+    WriteRegister(Register8, u8),
+    ReadPair(RegisterPair),
+    WritePair(RegisterPair, u16),
+    ReadPc,
+    WritePc(u16),
+    ReadSp,
+    WriteSp(u16),
+    AddBreakpoint(u16),
+    RemoveBreakpoint(u16),
+    Resume,
+    SingleStep,
+    Disassemble,
+}
+
+pub enum DebugResponse {
+    Register8(u8),
+    Register16(u16),
+    Mnemonic(String),
+    Ack,
+}
@@ -0,0 +1,106 @@
+#[derive(Debug)]
+pub enum Instruction {
+    ADD(ArithmeticTarget),
+    SUB(ArithmeticTarget),
+    SBC(ArithmeticTarget),
+    CP(ArithmeticTarget),
+    DAA,
+    JP(JumpTest),
+    CALL(JumpTest),
+    RET(JumpTest),
+    PUSH(StackTarget),
+    POP(StackTarget),
+    EI,
+    DI,
+    RETI,
+    STOP,
+}
+
+#[derive(Debug)]
+pub enum ArithmeticTarget {
+    A,
+    B,
+    C,
+    D,
+    E,
+    H,
+    L,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum JumpTest {
+    NotZero,
+    NotCarry,
+    Zero,
+    Carry,
+    Always,
+}
+
+/// The register pairs that can be pushed onto or popped off of the stack.
+#[derive(Debug)]
+pub enum StackTarget {
+    BC,
+    DE,
+    HL,
+    AF,
+}
+
+impl Instruction {
+    pub fn from_byte(byte: u8, prefixed: bool) -> Option<Instruction> {
+        if prefixed {
+            Instruction::from_byte_prefixed(byte)
+        } else {
+            Instruction::from_byte_not_prefixed(byte)
+        }
+    }
+
+    fn from_byte_prefixed(_byte: u8) -> Option<Instruction> {
+        // CB-prefixed instructions aren't decoded yet.
+        None
+    }
+
+    fn from_byte_not_prefixed(byte: u8) -> Option<Instruction> {
+        match byte {
+            0x81 => Some(Instruction::ADD(ArithmeticTarget::C)),
+            0x91 => Some(Instruction::SUB(ArithmeticTarget::C)),
+            0x99 => Some(Instruction::SBC(ArithmeticTarget::C)),
+            0xB9 => Some(Instruction::CP(ArithmeticTarget::C)),
+            0x27 => Some(Instruction::DAA),
+
+            0xC3 => Some(Instruction::JP(JumpTest::Always)),
+            0xC2 => Some(Instruction::JP(JumpTest::NotZero)),
+            0xCA => Some(Instruction::JP(JumpTest::Zero)),
+            0xD2 => Some(Instruction::JP(JumpTest::NotCarry)),
+            0xDA => Some(Instruction::JP(JumpTest::Carry)),
+
+            0xCD => Some(Instruction::CALL(JumpTest::Always)),
+            0xC4 => Some(Instruction::CALL(JumpTest::NotZero)),
+            0xCC => Some(Instruction::CALL(JumpTest::Zero)),
+            0xD4 => Some(Instruction::CALL(JumpTest::NotCarry)),
+            0xDC => Some(Instruction::CALL(JumpTest::Carry)),
+
+            0xC9 => Some(Instruction::RET(JumpTest::Always)),
+            0xC0 => Some(Instruction::RET(JumpTest::NotZero)),
+            0xC8 => Some(Instruction::RET(JumpTest::Zero)),
+            0xD0 => Some(Instruction::RET(JumpTest::NotCarry)),
+            0xD8 => Some(Instruction::RET(JumpTest::Carry)),
+
+            0xC5 => Some(Instruction::PUSH(StackTarget::BC)),
+            0xD5 => Some(Instruction::PUSH(StackTarget::DE)),
+            0xE5 => Some(Instruction::PUSH(StackTarget::HL)),
+            0xF5 => Some(Instruction::PUSH(StackTarget::AF)),
+
+            0xC1 => Some(Instruction::POP(StackTarget::BC)),
+            0xD1 => Some(Instruction::POP(StackTarget::DE)),
+            0xE1 => Some(Instruction::POP(StackTarget::HL)),
+            0xF1 => Some(Instruction::POP(StackTarget::AF)),
+
+            0xF3 => Some(Instruction::DI),
+            0xFB => Some(Instruction::EI),
+            0xD9 => Some(Instruction::RETI),
+            0x10 => Some(Instruction::STOP),
+
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/// The five hardware interrupt sources, in priority order (lowest bit wins
+/// when more than one is pending at once).
+pub enum Interrupt {
+    VBlank,
+    LCDStat,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    /// The bit this interrupt occupies in both the IE (0xFFFF) and IF (0xFF0F) registers.
+    pub fn bit(&self) -> u8 {
+        match self {
+            Interrupt::VBlank => 1 << 0,
+            Interrupt::LCDStat => 1 << 1,
+            Interrupt::Timer => 1 << 2,
+            Interrupt::Serial => 1 << 3,
+            Interrupt::Joypad => 1 << 4,
+        }
+    }
+
+    /// The fixed address execution jumps to when this interrupt is serviced.
+    pub fn vector(&self) -> u16 {
+        match self {
+            Interrupt::VBlank => 0x40,
+            Interrupt::LCDStat => 0x48,
+            Interrupt::Timer => 0x50,
+            Interrupt::Serial => 0x58,
+            Interrupt::Joypad => 0x60,
+        }
+    }
+
+    /// Picks the highest-priority interrupt out of a combined IE & IF byte.
+    pub fn from_bits(bits: u8) -> Option<Interrupt> {
+        [
+            Interrupt::VBlank,
+            Interrupt::LCDStat,
+            Interrupt::Timer,
+            Interrupt::Serial,
+            Interrupt::Joypad,
+        ]
+        .into_iter()
+        .find(|interrupt| bits & interrupt.bit() != 0)
+    }
+}
@@ -0,0 +1,58 @@
+const MEMORY_SIZE: usize = 0x10000;
+
+const INTERRUPT_ENABLE_ADDRESS: u16 = 0xFFFF;
+const INTERRUPT_FLAG_ADDRESS: u16 = 0xFF0F;
+const KEY1_ADDRESS: u16 = 0xFF4D;
+
+pub struct MemoryBus {
+    memory: [u8; MEMORY_SIZE]
+}
+
+impl Default for MemoryBus {
+    fn default() -> MemoryBus {
+        MemoryBus::new()
+    }
+}
+
+impl MemoryBus {
+    pub fn new() -> MemoryBus {
+        MemoryBus {
+            memory: [0; MEMORY_SIZE]
+        }
+    }
+
+    pub fn read_byte(&self, address: u16) -> u8 {
+        self.memory[address as usize]
+    }
+
+    pub fn write_byte(&mut self, address: u16, byte: u8) {
+        self.memory[address as usize] = byte;
+    }
+
+    /// The IE register: which interrupts the CPU is willing to service.
+    pub fn interrupt_enable(&self) -> u8 {
+        self.read_byte(INTERRUPT_ENABLE_ADDRESS)
+    }
+
+    pub fn set_interrupt_enable(&mut self, value: u8) {
+        self.write_byte(INTERRUPT_ENABLE_ADDRESS, value);
+    }
+
+    /// The IF register: which interrupts are currently requested.
+    pub fn interrupt_flag(&self) -> u8 {
+        self.read_byte(INTERRUPT_FLAG_ADDRESS)
+    }
+
+    pub fn set_interrupt_flag(&mut self, value: u8) {
+        self.write_byte(INTERRUPT_FLAG_ADDRESS, value);
+    }
+
+    /// The KEY1 register: CGB-only prepare/current speed switch bits.
+    pub fn key1(&self) -> u8 {
+        self.read_byte(KEY1_ADDRESS)
+    }
+
+    pub fn set_key1(&mut self, value: u8) {
+        self.write_byte(KEY1_ADDRESS, value);
+    }
+}
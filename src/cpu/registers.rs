@@ -1,15 +1,15 @@
 /// The Game Boy CPU contains 8 registers, each register is 8 bits (1 byte). The registers are labeled as: a, b, c, d, e, f, h, l.
 ///
 /// The CPU only has 8 bit registers, but there are instructions that can read and write 16 bits. We'll need "virtual" 16 bit registers, which are: af, bc, de, hl
-struct Registers {
-    a: u8,
-    b: u8,
-    c: u8,
-    d: u8,
-    e: u8,
-    f: FlagsRegister,
-    h: u8,
-    l: u8,
+pub(crate) struct Registers {
+    pub(crate) a: u8,
+    pub(crate) b: u8,
+    pub(crate) c: u8,
+    pub(crate) d: u8,
+    pub(crate) e: u8,
+    pub(crate) f: FlagsRegister,
+    pub(crate) h: u8,
+    pub(crate) l: u8,
 }
 
 /// ## Summary
@@ -20,11 +20,12 @@ struct Registers {
 /// - Bit 6: subtraction
 /// - Bit 5: half carry
 /// - Bit 4: carry
-struct FlagsRegister {
-    zero: bool,
-    subtract: bool,
-    half_carry: bool,
-    carry: bool
+#[derive(Copy, Clone)]
+pub(crate) struct FlagsRegister {
+    pub(crate) zero: bool,
+    pub(crate) subtract: bool,
+    pub(crate) half_carry: bool,
+    pub(crate) carry: bool
 }
 
 impl Registers {
@@ -33,18 +34,18 @@ impl Registers {
     /// f being the 8 right-most bits.
     /// ## Returns
     /// A 16 bit unsigned integer.
-    fn get_af(&self) -> u16 {
-        (self.a as u16) << 8 | self.f as u16
+    pub(crate) fn get_af(&self) -> u16 {
+        (self.a as u16) << 8 | u8::from(self.f) as u16
     }
 
     /// ## Summary
-    /// Sets the value of the "virtual" 16 bit register af. Register a will contain the 8 left-most bits and register f 
+    /// Sets the value of the "virtual" 16 bit register af. Register a will contain the 8 left-most bits and register f
     /// will contain the 8 right-most bits.
     /// ## Parameters
     /// - value: A 16 bit unsigned integer.
-    fn set_af(&mut self, value: u16) {
+    pub(crate) fn set_af(&mut self, value: u16) {
         self.a = ((value & 0xFF00) >> 8) as u8;
-        self.c = (value & 0xFF) as u8;
+        self.f = ((value & 0xFF) as u8).into();
     }
 
     /// ## Summary
@@ -52,7 +53,7 @@ impl Registers {
     /// c being the 8 right-most bits.
     /// ## Returns
     /// A 16 bit unsigned integer.
-    fn get_bc(&self) -> u16 {
+    pub(crate) fn get_bc(&self) -> u16 {
         (self.b as u16) << 8 | self.c as u16
     }
 
@@ -61,7 +62,7 @@ impl Registers {
     /// will contain the 8 right-most bits.
     /// ## Parameters
     /// - value: A 16 bit unsigned integer.
-    fn set_bc(&mut self, value: u16) {
+    pub(crate) fn set_bc(&mut self, value: u16) {
         self.b = ((value & 0xFF00) >> 8) as u8;
         self.c = (value & 0xFF) as u8;
     }
@@ -71,7 +72,7 @@ impl Registers {
     /// e being the 8 right-most bits.
     /// ## Returns
     /// A 16 bit unsigned integer.
-    fn get_de(&self) -> u16 {
+    pub(crate) fn get_de(&self) -> u16 {
         (self.d as u16) << 8 | self.e as u16
     }
 
@@ -80,7 +81,7 @@ impl Registers {
     /// will contain the 8 right-most bits.
     /// ## Parameters
     /// - value: A 16 bit unsigned integer.
-    fn set_de(&mut self, value: u16) {
+    pub(crate) fn set_de(&mut self, value: u16) {
         self.d = ((value & 0xFF00) >> 8) as u8;
         self.e = (value & 0xFF) as u8;
     }
@@ -90,7 +91,7 @@ impl Registers {
     /// l being the 8 right-most bits.
     /// ## Returns
     /// A 16 bit unsigned integer.
-    fn get_hl(&self) -> u16 {
+    pub(crate) fn get_hl(&self) -> u16 {
         (self.h as u16) << 8 | self.l as u16
     }
 
@@ -99,7 +100,7 @@ impl Registers {
     /// will contain the 8 right-most bits.
     /// ## Parameters
     /// - value: A 16 bit unsigned integer.
-    fn set_hl(&mut self, value: u16) {
+    pub(crate) fn set_hl(&mut self, value: u16) {
         self.h = ((value & 0xFF00) >> 8) as u8;
         self.l = (value & 0xFF) as u8;
     }
@@ -0,0 +1,38 @@
+/// Distinguishes the original DMG hardware from the backwards-compatible
+/// Game Boy Color (CGB), whose power-on register values and double-speed
+/// mode differ.
+pub enum Variant {
+    Dmg,
+    Cgb,
+}
+
+/// The register values hardware leaves behind once the boot ROM hands off
+/// control to the cartridge, per variant.
+pub struct PowerOnRegisters {
+    pub a: u8,
+    pub f: u8,
+    pub b: u8,
+    pub c: u8,
+    pub d: u8,
+    pub e: u8,
+    pub h: u8,
+    pub l: u8,
+}
+
+impl Variant {
+    pub fn power_on_registers(&self) -> PowerOnRegisters {
+        match self {
+            Variant::Dmg => PowerOnRegisters {
+                a: 0x01, f: 0xB0, b: 0x00, c: 0x13, d: 0x00, e: 0xD8, h: 0x01, l: 0x4D,
+            },
+            Variant::Cgb => PowerOnRegisters {
+                a: 0x11, f: 0x80, b: 0x00, c: 0x00, d: 0xFF, e: 0x56, h: 0x00, l: 0x0D,
+            },
+        }
+    }
+
+    /// Only the CGB can switch into double-speed mode via the KEY1 register.
+    pub fn supports_double_speed(&self) -> bool {
+        matches!(self, Variant::Cgb)
+    }
+}
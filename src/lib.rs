@@ -0,0 +1,6 @@
+// CPU/ADD/JP/CALL/RET/etc. mirror the Game Boy's actual opcode mnemonics;
+// lowercasing them per Rust naming conventions would make them harder to
+// cross-reference against opcode tables.
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod cpu;